@@ -0,0 +1,34 @@
+use crate::span::Span;
+
+/// Render a caret-style diagnostic pointing at `span` within `source`.
+///
+/// Produces output of the form:
+/// ```text
+///   |
+/// 1 | let x = 1 +
+///   |            ^ expected an expression
+/// ```
+/// and colorizes the line number and caret when stdout is a TTY.
+pub fn render(source: &str, span: &Span, message: &str) -> String {
+    let line_text = source.lines().nth(span.line.saturating_sub(1)).unwrap_or("");
+    let gutter = format!("{}", span.line);
+    let padding = " ".repeat(gutter.len());
+    let caret_offset = span.col.saturating_sub(1);
+    let caret = "^".repeat(span.len.max(1));
+
+    let (gutter, caret) = if use_color() {
+        (format!("\x1b[34m{gutter}\x1b[0m"), format!("\x1b[31m{caret}\x1b[0m"))
+    } else {
+        (gutter, caret)
+    };
+
+    format!(
+        "{padding} |\n{gutter} | {line_text}\n{padding} | {}{caret} {message}",
+        " ".repeat(caret_offset)
+    )
+}
+
+fn use_color() -> bool {
+    use std::io::IsTerminal;
+    std::io::stdout().is_terminal()
+}
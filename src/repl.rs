@@ -0,0 +1,61 @@
+use crate::diagnostics;
+use crate::parser::parse;
+use crate::runtime::Scope;
+use crate::source::Source;
+use crate::tokenizer::tokenize;
+use logger::Log;
+use std::io::{self, Write};
+
+/// Runs an interactive read-eval-print loop over stdin.
+///
+/// Each line is tokenized, parsed, and evaluated against a single
+/// `runtime::Scope` that persists for the lifetime of the REPL, so a `let`
+/// binding from one line stays visible to every line after it. Tokenize
+/// errors are printed as caret diagnostics, parse and eval errors as their
+/// log message, and in every case the loop continues rather than exiting.
+pub fn run() {
+    let mut scope: Option<Scope> = None;
+
+    loop {
+        print!("> ");
+        if io::stdout().flush().is_err() {
+            break;
+        }
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if let Err(err) = eval_line(&mut scope, line) {
+            eprintln!("{err}");
+        }
+    }
+}
+
+fn eval_line(scope: &mut Option<Scope>, line: String) -> Result<(), Box<Log>> {
+    if let Err(err) = tokenize(line.clone()) {
+        eprintln!("{}", diagnostics::render(&line, &err.span, &err.message));
+        return Ok(());
+    }
+
+    let ast = parse(Source::from(line))?;
+
+    let scope = match scope {
+        Some(scope) => {
+            scope.extend(ast);
+            scope
+        }
+        None => scope.insert(Scope::new(ast)),
+    };
+
+    if let Some(value) = scope.eval()? {
+        println!("{value:?}");
+    }
+
+    Ok(())
+}
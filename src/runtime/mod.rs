@@ -0,0 +1,90 @@
+pub mod types;
+
+use logger::{make_fatal, Log};
+use std::collections::HashMap;
+use types::Value;
+
+/// A single expression a statement can evaluate to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    Identifier(String),
+}
+
+/// A single parsed statement, as produced by `parser::parse`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Let(String, Expr),
+    Expr(Expr),
+}
+
+pub type Ast = Vec<Statement>;
+
+/// Holds the bindings accumulated while evaluating a program. Bindings made
+/// by `Statement::Let` persist on `self` for the lifetime of the `Scope`, so
+/// they remain visible to statements merged in later via `extend`.
+pub struct Scope {
+    statements: Ast,
+    bindings: HashMap<String, Value>,
+}
+
+impl Scope {
+    pub fn new(ast: Ast) -> Self {
+        Self { statements: ast, bindings: HashMap::new() }
+    }
+
+    /// Merges freshly parsed statements into this scope's pending program,
+    /// without disturbing bindings already made by earlier statements. This
+    /// is what lets a REPL evaluate one line at a time while keeping `let`
+    /// bindings from previous lines visible, instead of recreating the
+    /// `Scope` (and losing them) on every input.
+    pub fn extend(&mut self, ast: Ast) {
+        self.statements.extend(ast);
+    }
+
+    /// Evaluates every pending statement in order, returning the value of
+    /// the last bare expression (or `None` if the program ended on a `let`).
+    /// # Errors
+    /// This function will return an error if an identifier is referenced
+    /// before it is bound.
+    pub fn eval(&mut self) -> Result<Option<Value>, Box<Log>> {
+        let mut last = None;
+
+        for statement in self.statements.drain(..) {
+            last = match statement {
+                Statement::Let(name, expr) => {
+                    let value = Self::resolve(&self.bindings, &expr)?;
+                    self.bindings.insert(name, value);
+                    None
+                }
+                Statement::Expr(expr) => Some(Self::resolve(&self.bindings, &expr)?),
+            };
+        }
+
+        Ok(last)
+    }
+
+    fn resolve(bindings: &HashMap<String, Value>, expr: &Expr) -> Result<Value, Box<Log>> {
+        match expr {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Identifier(name) => bindings
+                .get(name)
+                .cloned()
+                .ok_or_else(|| Box::new(make_fatal!(format!("Undefined identifier: {name}")))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extend_keeps_earlier_bindings_visible() {
+        let mut scope = Scope::new(vec![Statement::Let("x".to_string(), Expr::Literal(Value::Number(1)))]);
+        assert_eq!(scope.eval().unwrap(), None);
+
+        scope.extend(vec![Statement::Expr(Expr::Identifier("x".to_string()))]);
+        assert_eq!(scope.eval().unwrap(), Some(Value::Number(1)));
+    }
+}
@@ -0,0 +1,23 @@
+/// A runtime value produced by evaluating `tl` source.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Number(u64),
+    Float(f64),
+    Bool(bool),
+    String(String),
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Value::Number(value) => serializer.serialize_u64(*value),
+            Value::Float(value) => serializer.serialize_f64(*value),
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::String(value) => serializer.serialize_str(value),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use crate::runtime::types::Value;
+use logger::{make_fatal, Log};
+
+/// Serializes a `Value` to a RON string.
+/// # Errors
+/// This function will return an error if serialization fails.
+#[cfg(feature = "serde")]
+pub fn to_ron_string(value: &Value) -> Result<String, Box<Log>> {
+    ron::ser::to_string(value).map_err(|err| Box::new(make_fatal!(format!("Could not serialize value to RON: {err}"))))
+}
+
+/// Serializes a `Value` to RON and writes it to `writer`.
+/// # Errors
+/// This function will return an error if serialization or writing fails.
+#[cfg(feature = "serde")]
+pub fn to_ron_writer<W: std::io::Write>(writer: W, value: &Value) -> Result<(), Box<Log>> {
+    ron::ser::to_writer(writer, value).map_err(|err| Box::new(make_fatal!(format!("Could not serialize value to RON: {err}"))))
+}
+
+/// Serializes a `Value` to a JSON string.
+/// # Errors
+/// This function will return an error if serialization fails.
+#[cfg(feature = "serde")]
+pub fn to_json_string(value: &Value) -> Result<String, Box<Log>> {
+    serde_json::to_string(value).map_err(|err| Box::new(make_fatal!(format!("Could not serialize value to JSON: {err}"))))
+}
+
+/// Serializes a `Value` to JSON and writes it to `writer`.
+/// # Errors
+/// This function will return an error if serialization or writing fails.
+#[cfg(feature = "serde")]
+pub fn to_json_writer<W: std::io::Write>(writer: W, value: &Value) -> Result<(), Box<Log>> {
+    serde_json::to_writer(writer, value).map_err(|err| Box::new(make_fatal!(format!("Could not serialize value to JSON: {err}"))))
+}
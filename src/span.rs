@@ -0,0 +1,28 @@
+/// A location in the original source text, used to point diagnostics back at
+/// the code that produced them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub col: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(line: usize, col: usize, start: usize, len: usize) -> Self {
+        Self { line, col, start, len }
+    }
+}
+
+/// Wraps a value together with the span of source it was produced from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Span,
+}
+
+impl<T> Spanned<T> {
+    pub fn new(node: T, span: Span) -> Self {
+        Self { node, span }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::utils::handle_string_escapes;
+use crate::span::{Span, Spanned};
 use std::{iter::Peekable, str::Chars};
 
 #[derive(Debug, PartialEq, PartialOrd)]
@@ -32,151 +32,505 @@ pub enum Token {
 
     // Misc
     Equals,
+
+    // Comparisons
+    EqEq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+
+    // Logic
+    AndAnd,
+    OrOr,
+}
+
+/// A lexing error, carrying the span in the original source that it occurred at.
+#[derive(Debug, PartialEq)]
+pub struct LexError {
+    pub message: String,
+    pub span: Span,
+}
+
+impl LexError {
+    fn new(message: impl Into<String>, span: Span) -> Self {
+        Self { message: message.into(), span }
+    }
+}
+
+/// Wraps a `Peekable<Chars>` and tracks the line/column/byte-offset of the
+/// cursor so every emitted token can carry a [`Span`] back to its source.
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    offset: usize,
+    line: usize,
+    col: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { chars: input.chars().peekable(), offset: 0, line: 1, col: 1 }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn peek_second(&self) -> Option<char> {
+        self.chars.clone().nth(1)
+    }
+
+    fn pos(&self) -> (usize, usize, usize) {
+        (self.line, self.col, self.offset)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        self.offset += ch.len_utf8();
+        if ch == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(ch)
+    }
+
+    fn span_since(&self, line: usize, col: usize, start: usize) -> Span {
+        Span::new(line, col, start, self.offset - start)
+    }
 }
 
-pub fn tokenize(input: impl Into<String>) -> Result<Vec<Token>, String> {
+pub fn tokenize(input: impl Into<String>) -> Result<Vec<Spanned<Token>>, LexError> {
     let input: String = input.into();
     let mut tokens = Vec::new();
-    let mut chars = input.chars().peekable();
+    let mut lexer = Lexer::new(&input);
 
     macro_rules! push_token {
-        ($token_type:ident) => {
-            tokens.push(Token::$token_type);
-            chars.next();
+        ($token_type:ident, $line:expr, $col:expr, $start:expr) => {
+            lexer.bump();
+            tokens.push(Spanned::new(Token::$token_type, lexer.span_since($line, $col, $start)));
         };
-        ($token_type:ident, $value:expr) => {
-            tokens.push(Token::$token_type($value));
-            chars.next();
+        ($token_type:ident, $value:expr, $line:expr, $col:expr, $start:expr) => {
+            lexer.bump();
+            tokens.push(Spanned::new(Token::$token_type($value), lexer.span_since($line, $col, $start)));
         };
     }
 
-    while let Some(&ch) = chars.peek() {
+    while let Some(ch) = lexer.peek() {
+        let (line, col, start) = lexer.pos();
+
         match ch {
             // Whitespace
             ' ' | '\t' | '\n' => {
-                chars.next();
+                lexer.bump();
                 continue;
             }
 
             // Comments / Slash operator
             '/' => {
-                if let Some(next_ch) = chars.clone().nth(1) {
-                    if next_ch == '/' {
-                        chars.next();
-                        chars.next();
-
-                        while let Some(&ch) = chars.peek() {
-                            if ch == '\n' {
-                                break;
-                            }
-                            chars.next();
+                if let Some('/') = lexer.peek_second() {
+                    lexer.bump();
+                    lexer.bump();
+
+                    while let Some(ch) = lexer.peek() {
+                        if ch == '\n' {
+                            break;
                         }
-                        continue;
+                        lexer.bump();
                     }
+                    continue;
                 }
 
-                push_token!(Slash);
+                push_token!(Slash, line, col, start);
             }
 
             // Do not try to simplify the match arm body, the push_token macro wont work if you do so.
 
             // Brackets
             '(' => {
-                push_token!(LParen);
+                push_token!(LParen, line, col, start);
             }
             ')' => {
-                push_token!(RParen);
+                push_token!(RParen, line, col, start);
             }
             '[' => {
-                push_token!(LBracket);
+                push_token!(LBracket, line, col, start);
             }
             ']' => {
-                push_token!(RBracket);
+                push_token!(RBracket, line, col, start);
             }
             '{' => {
-                push_token!(LBrace);
+                push_token!(LBrace, line, col, start);
             }
             '}' => {
-                push_token!(RBrace);
+                push_token!(RBrace, line, col, start);
             }
 
             // Operators
             '+' => {
-                push_token!(Plus);
+                push_token!(Plus, line, col, start);
             }
             '-' => {
-                push_token!(Minus);
+                push_token!(Minus, line, col, start);
             }
             '*' => {
-                push_token!(Multiply);
+                push_token!(Multiply, line, col, start);
             }
             '=' => {
-                push_token!(Equals);
+                lexer.bump();
+                if lexer.peek() == Some('=') {
+                    lexer.bump();
+                    tokens.push(Spanned::new(Token::EqEq, lexer.span_since(line, col, start)));
+                } else {
+                    tokens.push(Spanned::new(Token::Equals, lexer.span_since(line, col, start)));
+                }
+            }
+            '!' => {
+                lexer.bump();
+                if lexer.peek() == Some('=') {
+                    lexer.bump();
+                    tokens.push(Spanned::new(Token::NotEq, lexer.span_since(line, col, start)));
+                } else {
+                    return Err(LexError::new("Expected '=' after '!'", lexer.span_since(line, col, start)));
+                }
+            }
+            '<' => {
+                lexer.bump();
+                if lexer.peek() == Some('=') {
+                    lexer.bump();
+                    tokens.push(Spanned::new(Token::LtEq, lexer.span_since(line, col, start)));
+                } else {
+                    tokens.push(Spanned::new(Token::Lt, lexer.span_since(line, col, start)));
+                }
+            }
+            '>' => {
+                lexer.bump();
+                if lexer.peek() == Some('=') {
+                    lexer.bump();
+                    tokens.push(Spanned::new(Token::GtEq, lexer.span_since(line, col, start)));
+                } else {
+                    tokens.push(Spanned::new(Token::Gt, lexer.span_since(line, col, start)));
+                }
+            }
+            '&' => {
+                lexer.bump();
+                if lexer.peek() == Some('&') {
+                    lexer.bump();
+                    tokens.push(Spanned::new(Token::AndAnd, lexer.span_since(line, col, start)));
+                } else {
+                    return Err(LexError::new("Expected '&&'", lexer.span_since(line, col, start)));
+                }
+            }
+            '|' => {
+                lexer.bump();
+                if lexer.peek() == Some('|') {
+                    lexer.bump();
+                    tokens.push(Spanned::new(Token::OrOr, lexer.span_since(line, col, start)));
+                } else {
+                    return Err(LexError::new("Expected '||'", lexer.span_since(line, col, start)));
+                }
             }
 
             // Strings
-            '"' => tokens.push(tokenize_string(&mut chars)?),
+            '"' => tokens.push(tokenize_string(&mut lexer)?),
 
             // Mult-character tokens (literals, keywords, identifiers)
-            _ if ch.is_alphanumeric() || ch == '_' => tokens.extend(tokenize_multi_char(&mut chars)),
+            _ if ch.is_alphanumeric() || ch == '_' => tokens.extend(tokenize_multi_char(&mut lexer)),
 
-            _ => return Err(format!("Unexpected token: {ch}")),
+            _ => return Err(LexError::new(format!("Unexpected token: {ch}"), Span::new(line, col, start, ch.len_utf8()))),
         }
     }
 
     Ok(tokens)
 }
 
-pub fn tokenize_string(chars: &mut Peekable<Chars<'_>>) -> Result<Token, String> {
-    let mut closed: bool = false;
+fn tokenize_string(lexer: &mut Lexer<'_>) -> Result<Spanned<Token>, LexError> {
+    let (line, col, start) = lexer.pos();
+    let mut closed = false;
     let mut value = String::new();
-    chars.next();
+    lexer.bump();
 
-    while let Some(&ch) = chars.peek() {
+    while let Some(ch) = lexer.peek() {
         if ch == '"' {
-            chars.next();
+            lexer.bump();
             closed = true;
             break;
         }
 
+        if ch == '\\' {
+            lexer.bump();
+            value.push(read_escape(lexer, line, col, start)?);
+            continue;
+        }
+
         value.push(ch);
-        chars.next();
+        lexer.bump();
     }
 
     if !closed {
-        return Err("Unclosed string literal".to_string());
+        return Err(LexError::new("Unclosed string literal", lexer.span_since(line, col, start)));
     }
 
-    Ok(Token::String(handle_string_escapes(value)))
+    Ok(Spanned::new(Token::String(value), lexer.span_since(line, col, start)))
+}
+
+/// Reads a single escape sequence, with the cursor positioned just past the
+/// leading `\`. `line`/`col`/`start` locate the start of the *string literal*,
+/// so errors span from the start of the literal up to the bad escape rather
+/// than just the escape itself.
+fn read_escape(lexer: &mut Lexer<'_>, line: usize, col: usize, start: usize) -> Result<char, LexError> {
+    let Some(kind) = lexer.peek() else {
+        return Err(LexError::new("Unterminated escape sequence", lexer.span_since(line, col, start)));
+    };
+    lexer.bump();
+
+    Ok(match kind {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        '0' => '\0',
+        '"' => '"',
+        '\'' => '\'',
+        '\\' => '\\',
+
+        // `\xNN`: exactly two hex digits.
+        'x' => {
+            let mut digits = String::new();
+            for _ in 0..2 {
+                match lexer.peek() {
+                    Some(c) if c.is_ascii_hexdigit() => {
+                        digits.push(c);
+                        lexer.bump();
+                    }
+                    _ => return Err(LexError::new("Invalid \\x escape: expected two hex digits", lexer.span_since(line, col, start))),
+                }
+            }
+
+            // Exactly two hex digits means the value always fits a `u8` (<= 0xFF), which is always
+            // a valid scalar value (the surrogate range starts at 0xD800), so this
+            // conversion is infallible — unlike the braced `\u{...}` form below.
+            char::from(u8::from_str_radix(&digits, 16).unwrap())
+        }
+
+        // `\u{1F600}`: braced hex code point.
+        'u' => {
+            if lexer.peek() != Some('{') {
+                return Err(LexError::new("Invalid \\u escape: expected '{'", lexer.span_since(line, col, start)));
+            }
+            lexer.bump();
+
+            let mut digits = String::new();
+            while let Some(c) = lexer.peek()
+                && c != '}'
+            {
+                digits.push(c);
+                lexer.bump();
+            }
+
+            if lexer.peek() != Some('}') {
+                return Err(LexError::new("Invalid \\u escape: unterminated, expected '}'", lexer.span_since(line, col, start)));
+            }
+            lexer.bump();
+
+            let code = u32::from_str_radix(&digits, 16)
+                .map_err(|_| LexError::new("Invalid \\u escape: not a hex number", lexer.span_since(line, col, start)))?;
+            char::from_u32(code).ok_or_else(|| LexError::new("Invalid \\u escape: not a valid code point", lexer.span_since(line, col, start)))?
+        }
+
+        other => return Err(LexError::new(format!("Invalid escape sequence: \\{other}"), lexer.span_since(line, col, start))),
+    })
 }
 
-pub fn tokenize_multi_char(chars: &mut Peekable<Chars<'_>>) -> Vec<Token> {
+fn tokenize_multi_char(lexer: &mut Lexer<'_>) -> Vec<Spanned<Token>> {
+    let (line, col, start) = lexer.pos();
     let mut value = String::new();
-    let mut tokens = Vec::new();
 
-    while let Some(&ch) = chars.peek()
+    while let Some(ch) = lexer.peek()
         && (ch.is_alphanumeric() || ch == '_' || ch == '.')
     {
         value.push(ch);
-        chars.next();
+        lexer.bump();
+    }
+
+    // A `+`/`-` right after an exponent marker belongs to the exponent, e.g. `1.5e+10`.
+    // Gated on the value being a decimal numeric literal so far, so `rate-1`/`true-1`
+    // aren't swallowed, and so the trailing digit of a hex literal like `0x1E-5` isn't
+    // mistaken for an exponent marker (there `e`/`E` is a digit, not a marker).
+    if value.starts_with(|c: char| c.is_ascii_digit())
+        && !has_radix_prefix(&value)
+        && matches!(value.chars().last(), Some('e' | 'E'))
+        && let Some(sign @ ('+' | '-')) = lexer.peek()
+    {
+        value.push(sign);
+        lexer.bump();
+
+        while let Some(ch) = lexer.peek()
+            && (ch.is_ascii_digit() || ch == '_')
+        {
+            value.push(ch);
+            lexer.bump();
+        }
+    }
+
+    let span = lexer.span_since(line, col, start);
+    let token = match parse_numeric_literal(&value) {
+        Some(token) => token,
+        None => match value.as_str() {
+            // Boolean
+            "true" => Token::Bool(true),
+            "false" => Token::Bool(false),
+
+            // Keywords
+            "let" => Token::Let,
+            "import" => Token::Import,
+
+            // Identifier
+            _ => Token::Identifier(value),
+        },
+    };
+
+    vec![Spanned::new(token, span)]
+}
+
+/// Whether `value` starts with a `0x`/`0o`/`0b` radix prefix (case-insensitive).
+fn has_radix_prefix(value: &str) -> bool {
+    let prefix: String = value.chars().take(2).collect();
+    prefix.eq_ignore_ascii_case("0x") || prefix.eq_ignore_ascii_case("0o") || prefix.eq_ignore_ascii_case("0b")
+}
+
+/// Parses `0x`/`0o`/`0b`-prefixed, `_`-separated, and exponent-form numeric
+/// literals. Returns `None` (rather than an error) for anything that isn't a
+/// number at all, so the caller can fall back to keyword/identifier lookup.
+fn parse_numeric_literal(value: &str) -> Option<Token> {
+    if let Some(digits) = value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        return u64::from_str_radix(&strip_digit_separators(digits)?, 16).ok().map(Token::Number);
+    }
+    if let Some(digits) = value.strip_prefix("0o").or_else(|| value.strip_prefix("0O")) {
+        return u64::from_str_radix(&strip_digit_separators(digits)?, 8).ok().map(Token::Number);
+    }
+    if let Some(digits) = value.strip_prefix("0b").or_else(|| value.strip_prefix("0B")) {
+        return u64::from_str_radix(&strip_digit_separators(digits)?, 2).ok().map(Token::Number);
+    }
+
+    let is_float = value.contains(['.', 'e', 'E']);
+    let digits = strip_digit_separators(value)?;
+
+    if is_float {
+        digits.parse::<f64>().ok().map(Token::Float)
+    } else {
+        digits.parse::<u64>().ok().map(Token::Number)
+    }
+}
+
+/// Removes `_` digit separators, rejecting leading, trailing, or doubled underscores.
+fn strip_digit_separators(value: &str) -> Option<String> {
+    if value.is_empty() || value.starts_with('_') || value.ends_with('_') || value.contains("__") {
+        return None;
+    }
+
+    Some(value.replace('_', ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<Token> {
+        tokenize(input).unwrap().into_iter().map(|t| t.node).collect()
     }
 
-    match value.as_str() {
-        // Number / Float
-        _ if value.parse::<u64>().is_ok() => tokens.push(Token::Number(value.parse::<u64>().unwrap())),
-        _ if value.parse::<f64>().is_ok() => tokens.push(Token::Float(value.parse::<f64>().unwrap())),
+    #[test]
+    fn escaped_backslash_followed_by_literal_n_is_not_misdecoded() {
+        // Source text is `"\\n"`: an escaped backslash followed by a plain `n`.
+        // The old sequential-replace approach decoded this as a newline.
+        assert_eq!(tokens(r#""\\n""#), vec![Token::String("\\n".to_string())]);
+    }
+
+    #[test]
+    fn unicode_escape_decodes_braced_code_point() {
+        assert_eq!(tokens(r#""\u{1F600}""#), vec![Token::String("\u{1F600}".to_string())]);
+    }
+
+    #[test]
+    fn unicode_escape_rejects_empty_braces() {
+        let err = tokenize(r#""\u{}""#).unwrap_err();
+        assert!(err.message.contains("not a hex number"));
+    }
+
+    #[test]
+    fn unicode_escape_rejects_surrogate_code_point() {
+        let err = tokenize(r#""\u{D800}""#).unwrap_err();
+        assert!(err.message.contains("not a valid code point"));
+    }
+
+    #[test]
+    fn unicode_escape_rejects_out_of_range_code_point() {
+        let err = tokenize(r#""\u{110000}""#).unwrap_err();
+        assert!(err.message.contains("not a valid code point"));
+    }
+
+    #[test]
+    fn hex_escape_decodes_two_digits() {
+        assert_eq!(tokens(r#""\x41""#), vec![Token::String("A".to_string())]);
+    }
+
+    #[test]
+    fn hex_escape_rejects_fewer_than_two_digits() {
+        let err = tokenize(r#""\x4""#).unwrap_err();
+        assert!(err.message.contains("expected two hex digits"));
+    }
+
+    #[test]
+    fn hex_prefixed_literal_parses() {
+        assert_eq!(tokens("0xFF"), vec![Token::Number(255)]);
+    }
+
+    #[test]
+    fn octal_prefixed_literal_parses() {
+        assert_eq!(tokens("0o17"), vec![Token::Number(15)]);
+    }
+
+    #[test]
+    fn binary_prefixed_literal_parses() {
+        assert_eq!(tokens("0b101"), vec![Token::Number(5)]);
+    }
+
+    #[test]
+    fn digit_separators_are_stripped() {
+        assert_eq!(tokens("1_000_000"), vec![Token::Number(1_000_000)]);
+    }
 
-        // Boolean
-        "true" => tokens.push(Token::Bool(true)),
-        "false" => tokens.push(Token::Bool(false)),
+    #[test]
+    fn leading_underscore_after_radix_prefix_is_rejected() {
+        // Falls back to an identifier rather than silently dropping the separator.
+        assert_eq!(tokens("0x_FF"), vec![Token::Identifier("0x_FF".to_string())]);
+    }
+
+    #[test]
+    fn exponent_without_sign_parses_as_float() {
+        assert_eq!(tokens("1.5e10"), vec![Token::Float(1.5e10)]);
+    }
 
-        // Keywords
-        "let" => tokens.push(Token::Let),
-        "import" => tokens.push(Token::Import),
+    #[test]
+    fn exponent_with_sign_parses_as_float() {
+        assert_eq!(tokens("1.5e+10"), vec![Token::Float(1.5e10)]);
+    }
 
-        // Identifier
-        _ => tokens.push(Token::Identifier(value)),
+    #[test]
+    fn two_decimal_points_falls_back_to_identifier() {
+        assert_eq!(tokens("1.2.3"), vec![Token::Identifier("1.2.3".to_string())]);
     }
 
-    tokens
+    #[test]
+    fn overflowing_hex_literal_falls_back_to_identifier() {
+        let value = "0xFFFFFFFFFFFFFFFFF"; // 17 hex digits, wider than u64
+        assert_eq!(tokens(value), vec![Token::Identifier(value.to_string())]);
+    }
+
+    #[test]
+    fn hex_literal_trailing_e_is_not_mistaken_for_an_exponent() {
+        assert_eq!(tokens("0x1E-5"), vec![Token::Number(0x1E), Token::Minus, Token::Number(5)]);
+    }
 }